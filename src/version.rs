@@ -32,8 +32,11 @@
 //! [`Version`]: ./struct.Version.html
 //! [`parse`]: ./fn.parse.html
 
+use std::cmp::Ordering;
+use std::error;
 use std::fmt;
 use std::str::from_utf8;
+use std::str::FromStr;
 
 use recognize::*;
 
@@ -65,7 +68,15 @@ use common::{self, numeric_identifier};
 /// #   try_main().unwrap();
 /// # }
 /// ```
-#[derive(Clone, PartialOrd, Ord, Hash, Debug, PartialEq, Eq)]
+///
+/// `Version` orders by [SemVer precedence][semver-precedence] rather than by field order:
+/// `major`, `minor` and `patch` compare numerically, a version with pre-release identifiers
+/// is lower precedence than one without, and build metadata never affects ordering. This makes
+/// `Ord` deliberately inconsistent with the derived `Eq`, which does compare `build` — two
+/// versions that differ only in build metadata are unequal but compare as `Ordering::Equal`.
+///
+/// [semver-precedence]: https://semver.org/#spec-item-11
+#[derive(Clone, Hash, Debug, PartialEq, Eq)]
 pub struct Version {
     /// Major version as number (`0` in `"0.1.2"`).
     pub major: u64,
@@ -118,9 +129,67 @@ pub enum Identifier {
     AlphaNumeric(String),
 }
 
+/// An error encountered while parsing a [`Version`] from a string.
+///
+/// Unlike the `String` errors that `parse` returns, `SemVerError` lets callers match on the
+/// failure kind instead of scraping a message, and carries the byte offset into the trimmed
+/// input where parsing gave up.
+///
+/// [`Version`]: ./struct.Version.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SemVerError {
+    /// Parsing stopped at `position` expecting one thing and finding another.
+    IncorrectParse {
+        /// What parsing expected to find (e.g. `"dot"`, `"major version number"`).
+        expected: String,
+        /// What was actually found at `position`, if anything.
+        found: String,
+        /// Byte offset into the trimmed input where parsing stopped.
+        position: usize,
+    },
+    /// A pre-release or build identifier contained a byte outside the `[0-9A-Za-z-]`
+    /// alphabet SemVer allows there.
+    NonAsciiIdentifier {
+        /// Byte offset into the trimmed input of the offending byte.
+        position: usize,
+    },
+}
+
+impl fmt::Display for SemVerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SemVerError::IncorrectParse {
+                ref expected,
+                ref found,
+                position,
+            } => write!(
+                f,
+                "expected {} at position {}, found {:?}",
+                expected, position, found
+            ),
+            SemVerError::NonAsciiIdentifier { position } => write!(
+                f,
+                "invalid identifier character at position {}",
+                position
+            ),
+        }
+    }
+}
+
+impl error::Error for SemVerError {}
+
+impl FromStr for Version {
+    type Err = SemVerError;
+
+    fn from_str(version: &str) -> Result<Version, SemVerError> {
+        parse_version(version)
+    }
+}
+
 /// Function for parsing version string to [`Version`].
 ///
 /// Returns `Result<`[`Version`]`, String>`, where `String` represents an error while parsing.
+/// For a structured error, parse via [`FromStr`] instead (e.g. `version.parse::<Version>()`).
 ///
 /// # Examples
 ///
@@ -144,45 +213,144 @@ pub enum Identifier {
 /// # }
 /// ```
 /// [`Version`]: ./struct.Version.html
+/// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
 pub fn parse(version: &str) -> Result<Version, String> {
+    parse_version(version).map_err(|err| err.to_string())
+}
+
+fn found_at(s: &[u8], i: usize) -> String {
+    from_utf8(&s[i..]).unwrap_or("<invalid utf8>").to_string()
+}
+
+/// Rejects pre-release identifiers that are ambiguous leading-zero numbers, e.g. `01`.
+///
+/// `common::parse_optional_meta` classifies an all-digit segment with a leading zero as
+/// `AlphaNumeric` rather than `Numeric` (since it can't be a valid numeric identifier), which
+/// is the right call for build metadata, where such a segment is legal. A pre-release
+/// identifier, however, has no alphanumeric escape hatch: SemVer simply forbids leading zeros
+/// on a digits-only pre-release identifier, so we reject it here instead of silently letting it
+/// through as `AlphaNumeric`.
+fn reject_leading_zero_numeric_identifiers(
+    pre: &[Identifier],
+    offset: usize,
+) -> Result<(), SemVerError> {
+    // Identifiers follow the leading `-`, separated by single dots.
+    let mut position = offset + 1;
+    for identifier in pre {
+        if let Identifier::AlphaNumeric(ref id) = *identifier {
+            let bytes = id.as_bytes();
+            if bytes.len() > 1 && bytes[0] == b'0' && bytes.iter().all(u8::is_ascii_digit) {
+                return Err(SemVerError::IncorrectParse {
+                    expected: "numeric identifier without a leading zero".to_string(),
+                    found: id.clone(),
+                    position: position,
+                });
+            }
+        }
+        position += identifier.to_string().len() + 1;
+    }
+    Ok(())
+}
+
+/// `common::parse_optional_meta`'s recognizer only ever stops consuming at a byte outside
+/// `[0-9A-Za-z.-]` (or at end of input), so the bytes it *did* consume are always valid and
+/// there's nothing left to double-check there. What it can't tell us is *why* it stopped: a
+/// byte that isn't part of SemVer's identifier alphabet looks identical, from the recognizer's
+/// point of view, to correctly handing off to the next section (`+` after pre-release, or end
+/// of input after build metadata). This checks the single byte right after the section for
+/// that legitimate terminator, and reports `NonAsciiIdentifier` at its position if it's
+/// anything else — e.g. `!`, or the first byte of a multi-byte UTF-8 character.
+fn check_identifier_terminator(
+    s: &[u8],
+    end: usize,
+    terminator: Option<u8>,
+) -> Result<(), SemVerError> {
+    if end < s.len() && Some(s[end]) != terminator {
+        return Err(SemVerError::NonAsciiIdentifier { position: end });
+    }
+    Ok(())
+}
+
+fn parse_version(version: &str) -> Result<Version, SemVerError> {
     let s = version.trim().as_bytes();
     let mut i = 0;
     let major = if let Some((major, len)) = numeric_identifier(&s[i..]) {
         i += len;
         major
     } else {
-        return Err("Error parsing major identifier".to_string());
+        return Err(SemVerError::IncorrectParse {
+            expected: "major version number".to_string(),
+            found: found_at(s, i),
+            position: i,
+        });
     };
     if let Some(len) = b'.'.p(&s[i..]) {
         i += len;
     } else {
-        return Err("Expected dot".to_string());
+        return Err(SemVerError::IncorrectParse {
+            expected: "dot".to_string(),
+            found: found_at(s, i),
+            position: i,
+        });
     }
     let minor = if let Some((minor, len)) = numeric_identifier(&s[i..]) {
         i += len;
         minor
     } else {
-        return Err("Error parsing minor identifier".to_string());
+        return Err(SemVerError::IncorrectParse {
+            expected: "minor version number".to_string(),
+            found: found_at(s, i),
+            position: i,
+        });
     };
     if let Some(len) = b'.'.p(&s[i..]) {
         i += len;
     } else {
-        return Err("Expected dot".to_string());
+        return Err(SemVerError::IncorrectParse {
+            expected: "dot".to_string(),
+            found: found_at(s, i),
+            position: i,
+        });
     }
     let patch = if let Some((patch, len)) = numeric_identifier(&s[i..]) {
         i += len;
         patch
     } else {
-        return Err("Error parsing patch identifier".to_string());
+        return Err(SemVerError::IncorrectParse {
+            expected: "patch version number".to_string(),
+            found: found_at(s, i),
+            position: i,
+        });
     };
-    let (pre, pre_len) = common::parse_optional_meta(&s[i..], b'-')?;
+    let (pre, pre_len) = common::parse_optional_meta(&s[i..], b'-').map_err(|_| {
+        SemVerError::IncorrectParse {
+            expected: "valid pre-release identifiers".to_string(),
+            found: found_at(s, i),
+            position: i,
+        }
+    })?;
+    if pre_len > 0 {
+        check_identifier_terminator(s, i + pre_len, Some(b'+'))?;
+    }
+    reject_leading_zero_numeric_identifiers(&pre, i)?;
     i += pre_len;
-    let (build, build_len) = common::parse_optional_meta(&s[i..], b'+')?;
+    let (build, build_len) = common::parse_optional_meta(&s[i..], b'+').map_err(|_| {
+        SemVerError::IncorrectParse {
+            expected: "valid build metadata identifiers".to_string(),
+            found: found_at(s, i),
+            position: i,
+        }
+    })?;
+    if build_len > 0 {
+        check_identifier_terminator(s, i + build_len, None)?;
+    }
     i += build_len;
     if i != s.len() {
-        return Err(
-            "Extra junk after valid version: ".to_string() + from_utf8(&s[i..]).unwrap(),
-        );
+        return Err(SemVerError::IncorrectParse {
+            expected: "end of input".to_string(),
+            found: found_at(s, i),
+            position: i,
+        });
     }
     Ok(Version {
         major: major,
@@ -193,6 +361,86 @@ pub fn parse(version: &str) -> Result<Version, String> {
     })
 }
 
+impl Version {
+    /// Increments the patch version, clearing `pre` and `build`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use semver_parser::version;
+    ///
+    /// let mut version = version::parse("1.2.3-alpha+build").unwrap();
+    /// version.increment_patch();
+    /// assert_eq!(version::parse("1.2.4").unwrap(), version);
+    /// ```
+    pub fn increment_patch(&mut self) {
+        self.patch += 1;
+        self.pre.clear();
+        self.build.clear();
+    }
+
+    /// Increments the minor version and resets `patch` to `0`, clearing `pre` and `build`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use semver_parser::version;
+    ///
+    /// let mut version = version::parse("1.2.3-alpha+build").unwrap();
+    /// version.increment_minor();
+    /// assert_eq!(version::parse("1.3.0").unwrap(), version);
+    /// ```
+    pub fn increment_minor(&mut self) {
+        self.minor += 1;
+        self.patch = 0;
+        self.pre.clear();
+        self.build.clear();
+    }
+
+    /// Increments the major version and resets `minor` and `patch` to `0`, clearing `pre`
+    /// and `build`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use semver_parser::version;
+    ///
+    /// let mut version = version::parse("1.2.3-alpha+build").unwrap();
+    /// version.increment_major();
+    /// assert_eq!(version::parse("2.0.0").unwrap(), version);
+    /// ```
+    pub fn increment_major(&mut self) {
+        self.major += 1;
+        self.minor = 0;
+        self.patch = 0;
+        self.pre.clear();
+        self.build.clear();
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Version) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                // `Identifier`'s derived `Ord` already encodes SemVer's per-identifier rule
+                // (`Numeric` always lower than `AlphaNumeric`, equal variants compared by
+                // value) and `Vec`'s derived `Ord` breaks a shared prefix by length, which is
+                // exactly "more identifiers wins" when all shared ones are equal.
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
 impl fmt::Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         try!(write!(f, "{}.{}.{}", self.major, self.minor, self.patch));
@@ -217,8 +465,65 @@ impl fmt::Display for Identifier {
     }
 }
 
+/// `serde` support for [`Version`], gated behind the `serde` feature.
+///
+/// A `Version` round-trips through a single string field, matching how versions appear in
+/// `Cargo.toml` and lockfiles, rather than serializing as a struct of `major`/`minor`/`patch`/
+/// `pre`/`build` sub-fields.
+///
+/// [`Version`]: ./struct.Version.html
+#[cfg(feature = "serde")]
+mod serde_impl {
+    extern crate serde;
+
+    use std::fmt;
+
+    use self::serde::de::{self, Visitor};
+    use self::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Version;
+
+    impl Serialize for Version {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Version {
+        fn deserialize<D>(deserializer: D) -> Result<Version, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct VersionVisitor;
+
+            impl<'de> Visitor<'de> for VersionVisitor {
+                type Value = Version;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("a SemVer version string")
+                }
+
+                fn visit_str<E>(self, value: &str) -> Result<Version, E>
+                where
+                    E: de::Error,
+                {
+                    value.parse().map_err(de::Error::custom)
+                }
+            }
+
+            deserializer.deserialize_str(VersionVisitor)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "serde")]
+    extern crate serde_json;
+
     use version;
     use super::*;
 
@@ -534,4 +839,163 @@ mod tests {
         let expected_pre = vec![Identifier::AlphaNumeric(String::from("WIP"))];
         assert_eq!(expected_pre, parsed.pre);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trips_through_display_string() {
+        let version = version::parse("1.2.3-alpha+build").unwrap();
+
+        let json = self::serde_json::to_string(&version).unwrap();
+        assert_eq!("\"1.2.3-alpha+build\"", json);
+
+        let round_tripped: Version = self::serde_json::from_str(&json).unwrap();
+        assert_eq!(version, round_tripped);
+    }
+
+    #[test]
+    fn from_str_parses_via_trait() {
+        let version: Version = "1.2.3-alpha1".parse().unwrap();
+
+        assert_eq!(1, version.major);
+        assert_eq!(2, version.minor);
+        assert_eq!(3, version.patch);
+    }
+
+    #[test]
+    fn from_str_reports_position_of_failure() {
+        let err = "1.2".parse::<Version>().unwrap_err();
+
+        assert_eq!(
+            SemVerError::IncorrectParse {
+                expected: "dot".to_string(),
+                found: "".to_string(),
+                position: 3,
+            },
+            err
+        );
+    }
+
+    #[test]
+    fn from_str_reports_what_was_actually_found_for_bad_prerelease() {
+        let err = "1.2.3-".parse::<Version>().unwrap_err();
+
+        assert_eq!(
+            SemVerError::IncorrectParse {
+                expected: "valid pre-release identifiers".to_string(),
+                found: "-".to_string(),
+                position: 5,
+            },
+            err
+        );
+    }
+
+    #[test]
+    fn parse_rejects_leading_zero_numeric_prerelease() {
+        let version = "1.2.3-01";
+
+        let parsed = version::parse(version);
+
+        assert!(
+            parsed.is_err(),
+            "'{}' incorrectly considered a valid parse",
+            version
+        );
+    }
+
+    #[test]
+    fn parse_keeps_leading_zero_build_as_alphanumeric() {
+        let version = "1.2.3+01";
+
+        let parsed = version::parse(version).unwrap();
+
+        let expected_build = vec![Identifier::AlphaNumeric(String::from("01"))];
+        assert_eq!(expected_build, parsed.build);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_identifier_character() {
+        let err = "1.2.3-alpha!".parse::<Version>().unwrap_err();
+
+        match err {
+            SemVerError::NonAsciiIdentifier { .. } => {}
+            other => panic!("expected NonAsciiIdentifier, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_rejects_multibyte_identifier() {
+        let err = "1.2.3-café".parse::<Version>().unwrap_err();
+
+        match err {
+            SemVerError::NonAsciiIdentifier { .. } => {}
+            other => panic!("expected NonAsciiIdentifier, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_rejects_invalid_build_identifier_character() {
+        let err = "1.2.3+abc.d!e".parse::<Version>().unwrap_err();
+
+        match err {
+            SemVerError::NonAsciiIdentifier { .. } => {}
+            other => panic!("expected NonAsciiIdentifier, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn increment_patch_bumps_patch_and_clears_pre_and_build() {
+        let mut version = version::parse("1.2.3-alpha+build").unwrap();
+        version.increment_patch();
+
+        assert_eq!(version::parse("1.2.4").unwrap(), version);
+    }
+
+    #[test]
+    fn increment_minor_resets_patch_and_clears_pre_and_build() {
+        let mut version = version::parse("1.2.3-alpha+build").unwrap();
+        version.increment_minor();
+
+        assert_eq!(version::parse("1.3.0").unwrap(), version);
+    }
+
+    #[test]
+    fn increment_major_resets_minor_and_patch_and_clears_pre_and_build() {
+        let mut version = version::parse("1.2.3-alpha+build").unwrap();
+        version.increment_major();
+
+        assert_eq!(version::parse("2.0.0").unwrap(), version);
+    }
+
+    #[test]
+    fn cmp_pre_release_is_lower_than_release() {
+        let release = version::parse("1.0.0").unwrap();
+        let pre_release = version::parse("1.0.0-alpha").unwrap();
+
+        assert!(pre_release < release);
+    }
+
+    #[test]
+    fn cmp_numeric_identifier_is_lower_than_alphanumeric() {
+        let numeric = version::parse("1.0.0-1").unwrap();
+        let alphanumeric = version::parse("1.0.0-alpha").unwrap();
+
+        assert!(numeric < alphanumeric);
+    }
+
+    #[test]
+    fn cmp_more_identifiers_is_higher_precedence() {
+        let shorter = version::parse("1.0.0-alpha").unwrap();
+        let longer = version::parse("1.0.0-alpha.1").unwrap();
+
+        assert!(shorter < longer);
+    }
+
+    #[test]
+    fn cmp_ignores_build_metadata() {
+        let a = version::parse("1.0.0+build1").unwrap();
+        let b = version::parse("1.0.0+build2").unwrap();
+
+        assert_eq!(Ordering::Equal, a.cmp(&b));
+        assert_ne!(a, b, "build metadata must still participate in Eq");
+    }
 }